@@ -1,5 +1,40 @@
-use std::os::raw::{c_void, c_ulong};
+use std::fmt;
+use std::marker::PhantomData;
 use std::mem;
+use std::ops::Range;
+use std::os::raw::{c_void, c_ulong};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Error returned by the fallible `DeviceVec` allocation paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceAllocError {
+    /// The requested element count overflows `usize`, either on its own
+    /// (`len + additional`) or once multiplied by `size_of::<T>()`.
+    CapacityOverflow,
+    /// `gpu_malloc` returned a null pointer, i.e. the device is out of
+    /// memory.
+    DeviceAllocFailed { requested_bytes: usize },
+}
+
+impl fmt::Display for DeviceAllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CapacityOverflow => write!(f, "capacity overflow"),
+            Self::DeviceAllocFailed { requested_bytes } => {
+                write!(f, "gpu_malloc failed to allocate {requested_bytes} bytes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DeviceAllocError {}
+
+/// Computes `n * size_of::<T>()` as a `c_ulong`, checking for overflow.
+fn checked_bytes<T>(n: usize) -> Result<c_ulong, DeviceAllocError> {
+    n.checked_mul(mem::size_of::<T>())
+        .map(|bytes| bytes as c_ulong)
+        .ok_or(DeviceAllocError::CapacityOverflow)
+}
 
 extern "C" {
     pub fn gpu_malloc(size: c_ulong) -> *mut c_void;
@@ -12,34 +47,206 @@ extern "C" {
 pub struct DeviceVec<T: Copy> {
     ptr: *mut T,
     len: usize,
+    cap: usize,
 }
 
 impl<T: Copy> DeviceVec<T> {
+    /// Zero-sized types need no device allocation at all: we only ever
+    /// track a count, following the stdlib `Vec` approach of special-
+    /// casing `T::IS_ZST` everywhere a pointer would otherwise be
+    /// allocated, copied into, or freed.
+    const IS_ZST: bool = mem::size_of::<T>() == 0;
+
     pub fn len(&self) -> usize {
         self.len
     }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
     pub fn as_device_ptr(&self) -> *const T {
         self.ptr
     }
     pub fn as_mut_device_ptr(&mut self) -> *mut T {
         self.ptr
     }
+
+    /// Borrows a read-only sub-range `[range.start, range.end)` of this
+    /// buffer with no allocation or copy. The returned slice's lifetime
+    /// prevents it from outliving its owner.
+    pub fn slice(&self, range: Range<usize>) -> DeviceSlice<'_, T> {
+        assert!(range.start <= range.end && range.end <= self.len, "DeviceVec::slice: range out of bounds");
+        DeviceSlice {
+            ptr: unsafe { self.ptr.add(range.start) },
+            len: range.end - range.start,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Borrows a mutable sub-range `[range.start, range.end)` of this
+    /// buffer with no allocation or copy.
+    pub fn slice_mut(&mut self, range: Range<usize>) -> DeviceSliceMut<'_, T> {
+        assert!(range.start <= range.end && range.end <= self.len, "DeviceVec::slice_mut: range out of bounds");
+        DeviceSliceMut {
+            ptr: unsafe { self.ptr.add(range.start) },
+            len: range.end - range.start,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Allocates a device buffer with room for `cap` elements and no
+    /// initialized elements.
+    pub fn with_capacity(cap: usize) -> Self {
+        if Self::IS_ZST || cap == 0 {
+            return Self { ptr: std::ptr::NonNull::dangling().as_ptr(), len: 0, cap };
+        }
+        let bytes = checked_bytes::<T>(cap).expect("DeviceVec::with_capacity: capacity overflow");
+        let ptr = unsafe { gpu_malloc(bytes) } as *mut T;
+        if ptr.is_null() {
+            panic!("{}", DeviceAllocError::DeviceAllocFailed { requested_bytes: bytes as usize });
+        }
+        Self { ptr, len: 0, cap }
+    }
+
+    /// Fallible version of [`reserve`](Self::reserve): ensures there is
+    /// room for at least `additional` more elements, growing the device
+    /// allocation (by doubling) if necessary.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), DeviceAllocError> {
+        if Self::IS_ZST {
+            self.cap = usize::MAX;
+            return Ok(());
+        }
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or(DeviceAllocError::CapacityOverflow)?;
+        if required <= self.cap {
+            return Ok(());
+        }
+        let new_cap = self.cap.saturating_mul(2).max(required);
+        let new_bytes = checked_bytes::<T>(new_cap)?;
+        let used_bytes = (self.len * mem::size_of::<T>()) as c_ulong;
+        unsafe {
+            let new_ptr = gpu_malloc(new_bytes) as *mut T;
+            if new_ptr.is_null() {
+                return Err(DeviceAllocError::DeviceAllocFailed { requested_bytes: new_bytes as usize });
+            }
+            if self.cap != 0 {
+                gpu_memcpy_dtod(new_ptr as *mut c_void, self.ptr as *const c_void, used_bytes);
+                gpu_free(self.ptr as *mut c_void);
+            }
+            self.ptr = new_ptr;
+        }
+        self.cap = new_cap;
+        Ok(())
+    }
+
+    /// Ensures there is room for at least `additional` more elements,
+    /// growing the device allocation (by doubling) if necessary. Panics
+    /// on allocation failure; see [`try_reserve`](Self::try_reserve) for
+    /// a fallible version.
+    pub fn reserve(&mut self, additional: usize) {
+        self.try_reserve(additional).expect("DeviceVec::reserve");
+    }
+
+    /// Appends `value` to the end of the vector, growing the device
+    /// allocation if there is no spare capacity.
+    pub fn push(&mut self, value: T) {
+        if Self::IS_ZST {
+            self.len += 1;
+            return;
+        }
+        self.reserve(1);
+        let bytes = mem::size_of::<T>() as c_ulong;
+        unsafe {
+            let slot = self.ptr.add(self.len);
+            gpu_memcpy_htod(slot as *mut c_void, &value as *const T as *const c_void, bytes);
+        }
+        self.len += 1;
+    }
+
+    /// Resizes the vector to `new_len`, filling any newly created slots
+    /// with `value`. Shrinking simply truncates the logical length; the
+    /// underlying allocation is left untouched.
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        if new_len <= self.len || Self::IS_ZST {
+            self.len = new_len;
+            return;
+        }
+        self.reserve(new_len - self.len);
+        let bytes = mem::size_of::<T>() as c_ulong;
+        for i in self.len..new_len {
+            unsafe {
+                let slot = self.ptr.add(i);
+                gpu_memcpy_htod(slot as *mut c_void, &value as *const T as *const c_void, bytes);
+            }
+        }
+        self.len = new_len;
+    }
 }
 
-impl<T: Copy> From<&[T]> for DeviceVec<T> {
-    fn from(slice: &[T]) -> Self {
-        let bytes = (slice.len() * mem::size_of::<T>()) as c_ulong;
+impl<T: Copy> DeviceVec<T> {
+    /// Fallible version of `From<&[T]>`: allocates a device buffer sized
+    /// to `slice` and copies it over, returning an error instead of
+    /// storing an invalid pointer on allocation failure.
+    pub fn try_from_slice(slice: &[T]) -> Result<Self, DeviceAllocError> {
+        if Self::IS_ZST || slice.is_empty() {
+            return Ok(Self {
+                ptr: std::ptr::NonNull::dangling().as_ptr(),
+                len: slice.len(),
+                cap: slice.len(),
+            });
+        }
+        let bytes = checked_bytes::<T>(slice.len())?;
         unsafe {
             let ptr = gpu_malloc(bytes);
+            if ptr.is_null() {
+                return Err(DeviceAllocError::DeviceAllocFailed { requested_bytes: bytes as usize });
+            }
             gpu_memcpy_htod(ptr, slice.as_ptr() as *const c_void, bytes);
-            Self {
+            Ok(Self {
                 ptr: ptr as *mut T,
                 len: slice.len(),
+                cap: slice.len(),
+            })
+        }
+    }
+
+    /// Fallible version of `Clone`: deep-copies the device buffer,
+    /// returning an error instead of storing an invalid pointer on
+    /// allocation failure.
+    pub fn try_clone(&self) -> Result<Self, DeviceAllocError> {
+        if Self::IS_ZST || self.len == 0 {
+            return Ok(Self {
+                ptr: std::ptr::NonNull::dangling().as_ptr(),
+                len: self.len,
+                cap: self.len,
+            });
+        }
+        let bytes = checked_bytes::<T>(self.len)?;
+        unsafe {
+            let ptr = gpu_malloc(bytes);
+            if ptr.is_null() {
+                return Err(DeviceAllocError::DeviceAllocFailed { requested_bytes: bytes as usize });
             }
+            gpu_memcpy_dtod(ptr, self.ptr as *const c_void, bytes);
+            Ok(Self {
+                ptr: ptr as *mut T,
+                len: self.len,
+                cap: self.len,
+            })
         }
     }
 }
 
+impl<T: Copy> From<&[T]> for DeviceVec<T> {
+    fn from(slice: &[T]) -> Self {
+        Self::try_from_slice(slice).expect("DeviceVec: device allocation failed")
+    }
+}
+
 impl<T: Copy> From<&Vec<T>> for DeviceVec<T> {
     fn from(vec: &Vec<T>) -> Self {
         vec.as_slice().into()
@@ -48,6 +255,9 @@ impl<T: Copy> From<&Vec<T>> for DeviceVec<T> {
 
 impl<T: Copy> From<&DeviceVec<T>> for Vec<T> where T: Default {
     fn from(dvec: &DeviceVec<T>) -> Self {
+        if DeviceVec::<T>::IS_ZST {
+            return vec![T::default(); dvec.len()];
+        }
         let mut hvec = vec![T::default(); dvec.len()];
         let bytes = (dvec.len() * mem::size_of::<T>()) as c_ulong;
         unsafe {
@@ -59,6 +269,9 @@ impl<T: Copy> From<&DeviceVec<T>> for Vec<T> where T: Default {
 
 impl<T: Copy> Drop for DeviceVec<T> {
     fn drop(&mut self) {
+        if Self::IS_ZST || self.cap == 0 {
+            return;
+        }
         unsafe {
             gpu_free(self.ptr as *mut c_void)
         }
@@ -67,18 +280,356 @@ impl<T: Copy> Drop for DeviceVec<T> {
 
 impl<T: Copy> Clone for DeviceVec<T> {
     fn clone(&self) -> Self {
-        let bytes = (self.len * mem::size_of::<T>()) as c_ulong;
+        self.try_clone().expect("DeviceVec::clone: device allocation failed")
+    }
+}
+
+struct ArcInner<T: Copy> {
+    count: AtomicUsize,
+    ptr: *mut T,
+    len: usize,
+}
+
+/// A reference-counted, read-mostly device buffer, analogous to `Arc<[T]>`.
+///
+/// Unlike `DeviceVec<T>`, cloning a `DeviceArc<T>` just bumps a host-side
+/// atomic refcount rather than performing a device-to-device copy, so
+/// large read-only grids (a static potential, an equation-of-state
+/// table) can be shared by many solver objects for O(1) cost.
+pub struct DeviceArc<T: Copy> {
+    inner: *mut ArcInner<T>,
+}
+
+impl<T: Copy> DeviceArc<T> {
+    pub fn len(&self) -> usize {
+        unsafe { (*self.inner).len }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn as_device_ptr(&self) -> *const T {
+        unsafe { (*self.inner).ptr as *const T }
+    }
+
+    fn strong_count(&self) -> usize {
+        unsafe { (*self.inner).count.load(Ordering::Acquire) }
+    }
+
+    /// Returns a mutable device pointer, deep-copying the buffer first if
+    /// it is currently shared with other `DeviceArc` handles.
+    pub fn make_mut(this: &mut Self) -> *mut T {
+        if this.strong_count() > 1 {
+            unsafe {
+                let len = (*this.inner).len;
+                let new_ptr = if DeviceVec::<T>::IS_ZST {
+                    std::ptr::NonNull::dangling().as_ptr()
+                } else {
+                    let bytes = (len * mem::size_of::<T>()) as c_ulong;
+                    let new_ptr = gpu_malloc(bytes) as *mut T;
+                    if new_ptr.is_null() {
+                        panic!(
+                            "{}",
+                            DeviceAllocError::DeviceAllocFailed {
+                                requested_bytes: bytes as usize
+                            }
+                        );
+                    }
+                    gpu_memcpy_dtod(new_ptr as *mut c_void, (*this.inner).ptr as *const c_void, bytes);
+                    new_ptr
+                };
+                (*this.inner).count.fetch_sub(1, Ordering::Release);
+                this.inner = Box::into_raw(Box::new(ArcInner {
+                    count: AtomicUsize::new(1),
+                    ptr: new_ptr,
+                    len,
+                }));
+            }
+        }
+        unsafe { (*this.inner).ptr }
+    }
+
+    /// Returns the exclusively-owned `DeviceVec` if `this` is the sole
+    /// handle to the buffer, without copying; otherwise hands `this`
+    /// back unchanged.
+    pub fn try_unwrap(this: Self) -> Result<DeviceVec<T>, Self> {
+        if this.strong_count() == 1 {
+            let this = mem::ManuallyDrop::new(this);
+            let inner = unsafe { Box::from_raw(this.inner) };
+            Ok(DeviceVec {
+                ptr: inner.ptr,
+                len: inner.len,
+                cap: inner.len,
+            })
+        } else {
+            Err(this)
+        }
+    }
+}
+
+impl<T: Copy> From<&[T]> for DeviceArc<T> {
+    fn from(slice: &[T]) -> Self {
+        DeviceVec::from(slice).into()
+    }
+}
+
+impl<T: Copy> From<DeviceVec<T>> for DeviceArc<T> {
+    fn from(dvec: DeviceVec<T>) -> Self {
+        let dvec = mem::ManuallyDrop::new(dvec);
+        let inner = Box::new(ArcInner {
+            count: AtomicUsize::new(1),
+            ptr: dvec.ptr,
+            len: dvec.len,
+        });
+        Self {
+            inner: Box::into_raw(inner),
+        }
+    }
+}
+
+impl<T: Copy> Clone for DeviceArc<T> {
+    fn clone(&self) -> Self {
         unsafe {
-            let ptr = gpu_malloc(bytes);
-            gpu_memcpy_dtod(ptr, self.ptr as *const c_void, bytes);
-            Self {
-                ptr: ptr as *mut T,
-                len: self.len,
-            }            
+            (*self.inner).count.fetch_add(1, Ordering::Relaxed);
+        }
+        Self { inner: self.inner }
+    }
+}
+
+impl<T: Copy> Drop for DeviceArc<T> {
+    fn drop(&mut self) {
+        unsafe {
+            if (*self.inner).count.fetch_sub(1, Ordering::Release) == 1 {
+                std::sync::atomic::fence(Ordering::Acquire);
+                if !DeviceVec::<T>::IS_ZST && (*self.inner).len != 0 {
+                    gpu_free((*self.inner).ptr as *mut c_void);
+                }
+                drop(Box::from_raw(self.inner));
+            }
+        }
+    }
+}
+
+/// Error returned when encoding a `DeviceVec` checkpoint blob with
+/// [`DeviceVec::to_bytes`] or decoding one with [`DeviceVec::from_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodecError {
+    /// The blob does not start with the expected magic tag.
+    BadMagic,
+    /// The blob's recorded element size does not match `size_of::<T>()`,
+    /// i.e. it was encoded for a different type.
+    ElementSizeMismatch { expected: usize, found: usize },
+    /// A buffer is too short: when decoding, the blob is shorter than the
+    /// header or than the payload its header records; when encoding, the
+    /// output buffer is smaller than `serialized_size()`.
+    BufferTooSmall { expected: usize, found: usize },
+    /// `gpu_malloc` returned a null pointer while allocating the decoded
+    /// buffer, i.e. the device is out of memory.
+    DeviceAllocFailed { requested_bytes: usize },
+    /// The blob's recorded element count, multiplied by its element size,
+    /// overflows `usize` — the blob is corrupt or was crafted to overflow
+    /// the size check that follows.
+    LengthOverflow { len: usize, elem_size: usize },
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "bad magic tag in DeviceVec checkpoint blob"),
+            Self::ElementSizeMismatch { expected, found } => {
+                write!(f, "element size mismatch: expected {expected}, found {found}")
+            }
+            Self::BufferTooSmall { expected, found } => {
+                write!(f, "buffer too small: expected at least {expected} bytes, found {found}")
+            }
+            Self::DeviceAllocFailed { requested_bytes } => {
+                write!(f, "gpu_malloc failed to allocate {requested_bytes} bytes")
+            }
+            Self::LengthOverflow { len, elem_size } => {
+                write!(f, "element count {len} overflows usize when multiplied by element size {elem_size}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+impl<T: Copy> DeviceVec<T> {
+    const MAGIC: [u8; 4] = *b"SFDV";
+    const HEADER_LEN: usize = 4 + 8 + 8;
+
+    /// Size in bytes of the blob [`to_bytes`](Self::to_bytes) would
+    /// produce, so callers can preallocate.
+    pub fn serialized_size(&self) -> usize {
+        Self::HEADER_LEN + self.len * mem::size_of::<T>()
+    }
+
+    /// Encodes this buffer as a little-endian framed blob: a header
+    /// (magic, element size, element count) followed by the raw element
+    /// bytes, fetched from the device with a single `gpu_memcpy_dtoh`.
+    /// `buf` must be at least [`serialized_size`](Self::serialized_size)
+    /// bytes long.
+    pub fn to_bytes(&self, buf: &mut [u8]) -> Result<(), CodecError> {
+        let needed = self.serialized_size();
+        if buf.len() < needed {
+            return Err(CodecError::BufferTooSmall { expected: needed, found: buf.len() });
+        }
+        buf[0..4].copy_from_slice(&Self::MAGIC);
+        buf[4..12].copy_from_slice(&(mem::size_of::<T>() as u64).to_le_bytes());
+        buf[12..20].copy_from_slice(&(self.len as u64).to_le_bytes());
+        if !Self::IS_ZST && self.len > 0 {
+            let bytes = (self.len * mem::size_of::<T>()) as c_ulong;
+            unsafe {
+                gpu_memcpy_dtoh(
+                    buf[Self::HEADER_LEN..needed].as_mut_ptr() as *mut c_void,
+                    self.ptr as *const c_void,
+                    bytes,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Decodes a blob produced by [`to_bytes`](Self::to_bytes), validating
+    /// the magic tag and that the stored element size matches
+    /// `size_of::<T>()`, then allocates a fresh device buffer and copies
+    /// the payload in with a single `gpu_memcpy_htod`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CodecError> {
+        if bytes.len() < Self::HEADER_LEN {
+            return Err(CodecError::BufferTooSmall { expected: Self::HEADER_LEN, found: bytes.len() });
+        }
+        if bytes[0..4] != Self::MAGIC {
+            return Err(CodecError::BadMagic);
+        }
+        let elem_size = u64::from_le_bytes(bytes[4..12].try_into().unwrap()) as usize;
+        if elem_size != mem::size_of::<T>() {
+            return Err(CodecError::ElementSizeMismatch { expected: mem::size_of::<T>(), found: elem_size });
+        }
+        let len = u64::from_le_bytes(bytes[12..20].try_into().unwrap()) as usize;
+        let payload_bytes = len
+            .checked_mul(elem_size)
+            .ok_or(CodecError::LengthOverflow { len, elem_size })?;
+        let needed = Self::HEADER_LEN
+            .checked_add(payload_bytes)
+            .ok_or(CodecError::LengthOverflow { len, elem_size })?;
+        if bytes.len() < needed {
+            return Err(CodecError::BufferTooSmall { expected: needed, found: bytes.len() });
+        }
+        if Self::IS_ZST {
+            return Ok(Self { ptr: std::ptr::NonNull::dangling().as_ptr(), len, cap: len });
+        }
+        let payload = &bytes[Self::HEADER_LEN..needed];
+        let alloc_bytes = payload_bytes as c_ulong;
+        unsafe {
+            let ptr = gpu_malloc(alloc_bytes);
+            if ptr.is_null() {
+                return Err(CodecError::DeviceAllocFailed { requested_bytes: alloc_bytes as usize });
+            }
+            gpu_memcpy_htod(ptr, payload.as_ptr() as *const c_void, alloc_bytes);
+            Ok(Self { ptr: ptr as *mut T, len, cap: len })
         }
     }
 }
 
+/// A borrowed, read-only view into a sub-range of a `DeviceVec<T>`, with
+/// no allocation or copy. The lifetime `'a` ties the slice to its owner
+/// so it cannot outlive the buffer it points into.
+pub struct DeviceSlice<'a, T: Copy> {
+    ptr: *const T,
+    len: usize,
+    _marker: PhantomData<&'a DeviceVec<T>>,
+}
+
+impl<'a, T: Copy> DeviceSlice<'a, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn as_device_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Re-slices this view, narrowing to `[range.start, range.end)`
+    /// relative to the current view.
+    pub fn slice(&self, range: Range<usize>) -> DeviceSlice<'a, T> {
+        assert!(range.start <= range.end && range.end <= self.len, "DeviceSlice::slice: range out of bounds");
+        DeviceSlice {
+            ptr: unsafe { self.ptr.add(range.start) },
+            len: range.end - range.start,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Copies just this sub-range back to the host via a single
+    /// `gpu_memcpy_dtoh`.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Default,
+    {
+        let mut hvec = vec![T::default(); self.len];
+        if !DeviceVec::<T>::IS_ZST && self.len > 0 {
+            let bytes = (self.len * mem::size_of::<T>()) as c_ulong;
+            unsafe {
+                gpu_memcpy_dtoh(hvec.as_mut_ptr() as *mut c_void, self.ptr as *const c_void, bytes);
+            }
+        }
+        hvec
+    }
+}
+
+/// A borrowed, mutable view into a sub-range of a `DeviceVec<T>`, with no
+/// allocation or copy. The lifetime `'a` ties the slice to its owner so
+/// it cannot outlive the buffer it points into.
+pub struct DeviceSliceMut<'a, T: Copy> {
+    ptr: *mut T,
+    len: usize,
+    _marker: PhantomData<&'a mut DeviceVec<T>>,
+}
+
+impl<'a, T: Copy> DeviceSliceMut<'a, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn as_device_ptr(&self) -> *const T {
+        self.ptr as *const T
+    }
+    pub fn as_mut_device_ptr(&mut self) -> *mut T {
+        self.ptr
+    }
+
+    /// Re-slices this view, narrowing to `[range.start, range.end)`
+    /// relative to the current view.
+    pub fn slice_mut(&mut self, range: Range<usize>) -> DeviceSliceMut<'_, T> {
+        assert!(range.start <= range.end && range.end <= self.len, "DeviceSliceMut::slice_mut: range out of bounds");
+        DeviceSliceMut {
+            ptr: unsafe { self.ptr.add(range.start) },
+            len: range.end - range.start,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Copies just this sub-range back to the host via a single
+    /// `gpu_memcpy_dtoh`.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Default,
+    {
+        let mut hvec = vec![T::default(); self.len];
+        if !DeviceVec::<T>::IS_ZST && self.len > 0 {
+            let bytes = (self.len * mem::size_of::<T>()) as c_ulong;
+            unsafe {
+                gpu_memcpy_dtoh(hvec.as_mut_ptr() as *mut c_void, self.ptr as *const c_void, bytes);
+            }
+        }
+        hvec
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +640,175 @@ mod tests {
         let dvec = DeviceVec::from(&hvec);
         assert_eq!(hvec, Vec::from(&dvec));
     }
+
+    #[test]
+    fn push_grows_amortized() {
+        let mut dvec = DeviceVec::with_capacity(1);
+        for i in 0..100 {
+            dvec.push(i);
+        }
+        assert_eq!(dvec.len(), 100);
+        assert!(dvec.capacity() >= 100);
+        let hvec: Vec<_> = (0..100).collect();
+        assert_eq!(hvec, Vec::from(&dvec));
+    }
+
+    #[test]
+    fn resize_grows_and_shrinks() {
+        let mut dvec = DeviceVec::from(&vec![1, 2, 3]);
+        dvec.resize(5, 9);
+        assert_eq!(Vec::from(&dvec), vec![1, 2, 3, 9, 9]);
+        dvec.resize(2, 0);
+        assert_eq!(dvec.len(), 2);
+        assert_eq!(Vec::from(&dvec), vec![1, 2]);
+    }
+
+    #[test]
+    fn try_reserve_reports_capacity_overflow() {
+        let mut dvec: DeviceVec<u64> = DeviceVec::with_capacity(1);
+        let err = dvec.try_reserve(usize::MAX).unwrap_err();
+        assert_eq!(err, DeviceAllocError::CapacityOverflow);
+    }
+
+    #[test]
+    fn try_clone_succeeds() {
+        let dvec = DeviceVec::from(&vec![1, 2, 3]);
+        let cloned = dvec.try_clone().unwrap();
+        assert_eq!(Vec::from(&dvec), Vec::from(&cloned));
+    }
+
+    #[test]
+    fn empty_non_zst_vec_drops_without_freeing_sentinel() {
+        drop(DeviceVec::<i32>::with_capacity(0));
+        drop(DeviceVec::<i32>::from(&[][..]));
+        let empty: DeviceVec<i32> = DeviceVec::from(&[][..]);
+        drop(empty.try_clone().unwrap());
+    }
+
+    #[test]
+    fn push_after_empty_with_capacity_does_not_free_sentinel() {
+        let mut dvec: DeviceVec<i32> = DeviceVec::with_capacity(0);
+        dvec.push(1);
+        assert_eq!(Vec::from(&dvec), vec![1]);
+    }
+
+    #[test]
+    fn push_after_from_empty_slice_does_not_free_sentinel() {
+        let mut dvec: DeviceVec<i32> = DeviceVec::from(&[][..]);
+        dvec.push(7);
+        assert_eq!(Vec::from(&dvec), vec![7]);
+    }
+
+    #[test]
+    fn device_arc_clone_shares_buffer() {
+        let arc = DeviceArc::from(&[1, 2, 3][..]);
+        assert_eq!(arc.strong_count(), 1);
+        let arc2 = arc.clone();
+        assert_eq!(arc.strong_count(), 2);
+        assert_eq!(arc.as_device_ptr(), arc2.as_device_ptr());
+        drop(arc2);
+        assert_eq!(arc.strong_count(), 1);
+    }
+
+    #[test]
+    fn device_arc_try_unwrap_sole_owner() {
+        let hvec = vec![1, 2, 3];
+        let arc = DeviceArc::from(&hvec[..]);
+        let dvec = DeviceArc::try_unwrap(arc).ok().unwrap();
+        assert_eq!(hvec, Vec::from(&dvec));
+    }
+
+    #[test]
+    fn device_arc_try_unwrap_shared_fails() {
+        let arc = DeviceArc::from(&[1, 2, 3][..]);
+        let arc2 = arc.clone();
+        let arc = DeviceArc::try_unwrap(arc).err().unwrap();
+        drop(arc);
+        drop(arc2);
+    }
+
+    #[test]
+    fn device_arc_from_empty_non_zst_slice_drops_without_freeing_sentinel() {
+        let arc: DeviceArc<i32> = DeviceArc::from(&[][..]);
+        let arc2 = arc.clone();
+        drop(arc);
+        drop(arc2);
+    }
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    struct ZstMarker;
+
+    #[test]
+    fn zst_round_trip_tracks_len_only() {
+        let hvec = vec![ZstMarker; 5];
+        let mut dvec = DeviceVec::from(&hvec);
+        assert_eq!(dvec.len(), 5);
+        dvec.push(ZstMarker);
+        dvec.resize(10, ZstMarker);
+        assert_eq!(dvec.len(), 10);
+        assert_eq!(Vec::from(&dvec), vec![ZstMarker; 10]);
+    }
+
+    #[test]
+    fn checkpoint_round_trip() {
+        let hvec: Vec<_> = (0..50).collect();
+        let dvec = DeviceVec::from(&hvec);
+        let mut buf = vec![0u8; dvec.serialized_size()];
+        dvec.to_bytes(&mut buf).unwrap();
+        let decoded: DeviceVec<i32> = DeviceVec::from_bytes(&buf).unwrap();
+        assert_eq!(hvec, Vec::from(&decoded));
+    }
+
+    #[test]
+    fn checkpoint_rejects_bad_magic() {
+        let mut buf = vec![0u8; DeviceVec::<i32>::HEADER_LEN];
+        buf[0..4].copy_from_slice(b"NOPE");
+        assert_eq!(DeviceVec::<i32>::from_bytes(&buf).err().unwrap(), CodecError::BadMagic);
+    }
+
+    #[test]
+    fn checkpoint_rejects_element_size_mismatch() {
+        let dvec = DeviceVec::from(&vec![1i32, 2, 3]);
+        let mut buf = vec![0u8; dvec.serialized_size()];
+        dvec.to_bytes(&mut buf).unwrap();
+        let err = DeviceVec::<i64>::from_bytes(&buf).err().unwrap();
+        assert_eq!(err, CodecError::ElementSizeMismatch { expected: 8, found: 4 });
+    }
+
+    #[test]
+    fn slice_copies_only_sub_range() {
+        let hvec: Vec<_> = (0..10).collect();
+        let dvec = DeviceVec::from(&hvec);
+        let sub = dvec.slice(3..7);
+        assert_eq!(sub.len(), 4);
+        assert_eq!(sub.to_vec(), vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn slice_mut_allows_reslicing() {
+        let hvec: Vec<_> = (0..10).collect();
+        let mut dvec = DeviceVec::from(&hvec);
+        let mut sub = dvec.slice_mut(2..8);
+        let narrower = sub.slice_mut(1..3);
+        assert_eq!(narrower.len(), 2);
+        assert_eq!(narrower.to_vec(), vec![3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn slice_out_of_bounds_panics() {
+        let dvec = DeviceVec::from(&vec![1, 2, 3]);
+        dvec.slice(2..5);
+    }
+
+    #[test]
+    fn device_arc_make_mut_copies_when_shared() {
+        let mut arc = DeviceArc::from(&[1, 2, 3][..]);
+        let arc2 = arc.clone();
+        let original_ptr = arc.as_device_ptr();
+        DeviceArc::make_mut(&mut arc);
+        assert_ne!(arc.as_device_ptr(), original_ptr);
+        assert_eq!(arc.strong_count(), 1);
+        drop(arc2);
+    }
 }
\ No newline at end of file